@@ -0,0 +1,119 @@
+//! local cache of resolved GitHub issue/PR titles, backed by sqlite
+//!
+//! modeled on build-o-tron's `dbctx.rs`: a thin wrapper around a single
+//! `rusqlite::Connection` that knows how to create its own schema and
+//! answer the handful of queries this tool needs.
+
+use anyhow::Context;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::path::Path;
+
+use crate::providers::WorkItem;
+use crate::providers::github::{GitHubKind, GitHubLink};
+
+/// Wraps the sqlite connection used to cache resolved GitHub titles
+pub struct DbCtx {
+    conn: Connection,
+}
+
+impl DbCtx {
+    /// Open (creating if necessary) the cache database at `path`
+    pub fn open<P: AsRef<Path>>(path: P) -> anyhow::Result<DbCtx> {
+        let conn = Connection::open(path.as_ref()).with_context(|| {
+            format!("failed to open cache db at {:?}", path.as_ref())
+        })?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS github_items (
+                owner      TEXT NOT NULL,
+                repo       TEXT NOT NULL,
+                kind       TEXT NOT NULL,
+                number     INTEGER NOT NULL,
+                url        TEXT NOT NULL,
+                title      TEXT NOT NULL,
+                fetched_at TEXT NOT NULL,
+                PRIMARY KEY (owner, repo, kind, number)
+            )",
+            (),
+        )
+        .context("failed to create github_items table")?;
+
+        Ok(DbCtx { conn })
+    }
+
+    /// Look up a cached title for `link`, if we have one fetched more
+    /// recently than `ttl` ago
+    pub fn lookup(
+        &self,
+        link: &GitHubLink,
+        ttl: Duration,
+    ) -> anyhow::Result<Option<WorkItem>> {
+        let kind = kind_str(&link.kind);
+        let row: Option<(String, String, String)> = self
+            .conn
+            .query_row(
+                "SELECT url, title, fetched_at FROM github_items
+                 WHERE owner = ?1 AND repo = ?2 AND kind = ?3 AND number = ?4",
+                params![link.owner, link.repo, kind, link.number],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()
+            .context("failed to query cache")?;
+
+        let Some((url, title, fetched_at)) = row else {
+            return Ok(None);
+        };
+
+        let fetched_at: DateTime<Utc> = fetched_at
+            .parse()
+            .context("failed to parse cached fetched_at timestamp")?;
+        if Utc::now() - fetched_at > ttl {
+            return Ok(None);
+        }
+
+        Ok(Some(WorkItem {
+            label: format!("{}/{}#{}", link.owner, link.repo, link.number),
+            url,
+            title,
+        }))
+    }
+
+    /// Insert or update the cached title for `link`
+    pub fn upsert(
+        &self,
+        link: &GitHubLink,
+        item: &WorkItem,
+        fetched_at: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        let kind = kind_str(&link.kind);
+        self.conn
+            .execute(
+                "INSERT INTO github_items
+                    (owner, repo, kind, number, url, title, fetched_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT (owner, repo, kind, number) DO UPDATE SET
+                    url = excluded.url,
+                    title = excluded.title,
+                    fetched_at = excluded.fetched_at",
+                params![
+                    link.owner,
+                    link.repo,
+                    kind,
+                    link.number,
+                    item.url,
+                    item.title,
+                    fetched_at.to_rfc3339(),
+                ],
+            )
+            .context("failed to upsert cache row")?;
+        Ok(())
+    }
+}
+
+fn kind_str(kind: &GitHubKind) -> &'static str {
+    match kind {
+        GitHubKind::Issue => "issue",
+        GitHubKind::PullRequest => "pull",
+    }
+}
@@ -1,23 +1,31 @@
 //! command-line tool for constructing my status update from Todoist
 
 use anyhow::{Context, anyhow};
+use chrono::Duration;
 use chrono::SecondsFormat;
 use chrono::{DateTime, Utc};
 use http::HeaderMap;
 use http::HeaderValue;
-use octocrab::{Octocrab, models::issues::Issue, models::pulls::PullRequest};
-use regex::Regex;
+use octocrab::Octocrab;
 use reqwest::Client;
 use serde::Deserialize;
 use std::collections::{BTreeMap, BTreeSet};
+use std::rc::Rc;
+
+mod dbctx;
+mod notifier;
+mod providers;
+mod report;
+mod watch;
+
+use dbctx::DbCtx;
+use providers::{LinkProvider, ParsedLink, WorkItem};
+use report::{Format, Report, ReportedItem, Section};
 
 // XXX-dap TODO:
 // - command-line argument for "since" date
-// - do something similar for RFD URLs that I do for GitHub ones
 // - get personal access token for Oxide organization
 // - ask about some kind of access token for RFD site?
-// - move the GitHub fetching to fetch-time instead of print-time so that we can
-//   report all that stuff at once
 
 static TODOIST_API_TOKEN: &str = include_str!("../todoist_token.txt");
 static GITHUB_API_TOKEN: &str = include_str!("../github_token.txt");
@@ -25,6 +33,20 @@ static GITHUB_API_TOKEN: &str = include_str!("../github_token.txt");
 // static TODOIST_API_URL: &str = "http://127.0.0.1:8080/sync/v9";
 static TODOIST_API_URL: &str = "https://api.todoist.com/sync/v9";
 
+/// default cache TTL for resolved GitHub titles, absent `--refresh`
+const CACHE_TTL_DEFAULT_HOURS: i64 = 24;
+
+/// Options controlling how (and whether) we use the local title cache
+#[derive(Clone, Copy)]
+pub struct CacheOptions {
+    /// don't read or write the cache at all
+    pub no_cache: bool,
+    /// ignore any cached entry and re-fetch from GitHub
+    pub refresh: bool,
+    /// how old a cached entry can be before we treat it as stale
+    pub ttl: Duration,
+}
+
 #[tokio::main]
 async fn main() {
     if let Err(error) = doit().await {
@@ -34,15 +56,58 @@ async fn main() {
 }
 
 async fn doit() -> Result<(), anyhow::Error> {
-    // Parse the "since" argument.
-    let since_arg = std::env::args()
-        .skip(1)
-        .next()
-        .ok_or_else(|| anyhow!("expected TIMESTAMP argument"))?;
+    // Parse arguments: a positional "since" timestamp plus optional
+    // `--no-cache` / `--refresh` / `--watch` / `--email CONFIG` /
+    // `--format FORMAT` flags.
+    let mut since_arg = None;
+    let mut email_config_path = None;
+    let mut watch_mode = false;
+    let mut format = Format::Markdown;
+    let mut cache_opts = CacheOptions {
+        no_cache: false,
+        refresh: false,
+        ttl: Duration::hours(CACHE_TTL_DEFAULT_HOURS),
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--no-cache" => cache_opts.no_cache = true,
+            "--refresh" => cache_opts.refresh = true,
+            "--watch" => watch_mode = true,
+            "--email" => {
+                email_config_path = Some(
+                    args.next()
+                        .ok_or_else(|| anyhow!("--email requires a CONFIG path"))?,
+                );
+            }
+            "--format" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| anyhow!("--format requires a value"))?;
+                format = value.parse()?;
+            }
+            _ if since_arg.is_none() => since_arg = Some(arg),
+            _ => return Err(anyhow!("unexpected argument: {}", arg)),
+        }
+    }
+    let since_arg =
+        since_arg.ok_or_else(|| anyhow!("expected TIMESTAMP argument"))?;
     let since: DateTime<Utc> = DateTime::parse_from_rfc3339(&since_arg)
         .context("expected RFC 3339 timestamp")?
         .to_utc();
 
+    // Open the local cache database next to the binary (unless disabled).
+    let db: Option<Rc<DbCtx>> = if cache_opts.no_cache {
+        None
+    } else {
+        let exe_dir = std::env::current_exe()
+            .context("failed to determine path to this binary")?
+            .parent()
+            .ok_or_else(|| anyhow!("binary path had no parent directory"))?
+            .to_owned();
+        Some(Rc::new(DbCtx::open(exe_dir.join("state.db"))?))
+    };
+
     // Set up client for talking to Todoist
     let mut headers = HeaderMap::new();
     headers.insert(
@@ -58,14 +123,43 @@ async fn doit() -> Result<(), anyhow::Error> {
 
     // Set up client for talking to GitHub
     let octocrab = Octocrab::builder()
-        .personal_token(GITHUB_API_TOKEN.trim())
+        .personal_token(GITHUB_API_TOKEN.trim().to_string())
         .build()
         .context("Failed to create Octocrab instance")?;
 
+    // Register the providers that know how to recognize and resolve links
+    // mentioned in item content.
+    let link_providers: Vec<Box<dyn LinkProvider>> = vec![
+        Box::new(providers::github::GitHubProvider::new(
+            octocrab.clone(),
+            db.clone(),
+            cache_opts,
+        )),
+        Box::new(providers::rfd::RfdProvider::new()),
+    ];
+
+    if watch_mode {
+        return watch::watch(
+            &client,
+            &link_providers,
+            since,
+            watch::DEFAULT_POLL_INTERVAL,
+        )
+        .await;
+    }
+
     // Fetch Todoist items
     let all_items = fetch_completed_tasks(&client, since).await?;
 
-    // Print a report.  Along the way, fetch GItHub links.
+    // Resolve every link across every item up front, concurrently, so the
+    // print phase below is just a lookup.
+    let all_links = all_items
+        .values()
+        .flatten()
+        .flat_map(|item| item.extract_links(&link_providers));
+    let resolved = providers::resolve_all(&link_providers, all_links).await;
+
+    // Print a report.
     let (reconfigurator_project, reconfigurator_items) = all_items
         .iter()
         .find(|(k, _)| k.starts_with("Oxide: Reconfigurator"))
@@ -81,31 +175,49 @@ async fn doit() -> Result<(), anyhow::Error> {
         })
         .flatten();
 
-    // Store which tasks we've printed to avoid printing the same one multiple
-    // times.  (This comes up for routines.)
+    // Store which tasks we've reported to avoid reporting the same one
+    // multiple times.  (This comes up for routines.)
     let mut printed = BTreeSet::new();
 
-    println!("RECONFIGURATOR ITEMS:");
-    for item in reconfigurator_items {
-        if !printed.insert(&item.task_id) {
-            continue;
-        }
-        println!("* {}", item.content);
-        for link in item.fetch_github_titles(&octocrab).await? {
-            println!("    * [{}]({}) ({:?})", link.label, link.url, link.title);
-        }
-    }
-
-    println!("\n\nOther work:");
-
-    for item in other_project_items {
-        if !printed.insert(&item.task_id) {
-            continue;
-        }
-        println!("* {}", item.content);
-        for link in item.fetch_github_titles(&octocrab).await? {
-            println!("    * [{}]({}) ({:?})", link.label, link.url, link.title);
-        }
+    let report = Report {
+        sections: vec![
+            Section {
+                title: "Reconfigurator".to_string(),
+                items: reconfigurator_items
+                    .iter()
+                    .filter(|item| printed.insert(&item.task_id))
+                    .map(|item| ReportedItem {
+                        content: item.content.clone(),
+                        work_items: item
+                            .resolved_work_items(&link_providers, &resolved)
+                            .into_iter()
+                            .cloned()
+                            .collect(),
+                    })
+                    .collect(),
+            },
+            Section {
+                title: "Other work".to_string(),
+                items: other_project_items
+                    .filter(|item| printed.insert(&item.task_id))
+                    .map(|item| ReportedItem {
+                        content: item.content.clone(),
+                        work_items: item
+                            .resolved_work_items(&link_providers, &resolved)
+                            .into_iter()
+                            .cloned()
+                            .collect(),
+                    })
+                    .collect(),
+            },
+        ],
+    };
+
+    if let Some(email_config_path) = &email_config_path {
+        let email_config = notifier::EmailConfig::load(email_config_path)?;
+        notifier::send_report(&email_config, &report)?;
+    } else {
+        print!("{}", report.render(format)?);
     }
 
     Ok(())
@@ -113,7 +225,7 @@ async fn doit() -> Result<(), anyhow::Error> {
 
 /// From Todoist, fetch all items completed since "since", grouped by each
 /// task's project's name.
-async fn fetch_completed_tasks(
+pub(crate) async fn fetch_completed_tasks(
     client: &Client,
     since: DateTime<Utc>,
 ) -> anyhow::Result<BTreeMap<String, Vec<Item>>> {
@@ -183,9 +295,9 @@ struct CompletedItems {
 /// There can be many of these for one task if it's a recurring task that was
 /// completed multiple times.
 #[derive(Debug, Deserialize)]
-struct Item {
-    content: String,
-    task_id: String,
+pub(crate) struct Item {
+    pub(crate) content: String,
+    pub(crate) task_id: String,
     project_id: String,
 }
 
@@ -194,114 +306,25 @@ struct Project {
     name: String,
 }
 
-/// Describes a parsed link to a GitHub issue or pull request
-#[derive(Debug)]
-struct GitHubLink {
-    owner: String,
-    repo: String,
-    kind: GitHubKind,
-    number: u64,
-}
-
-#[derive(Debug)]
-enum GitHubKind {
-    Issue,
-    PullRequest,
-}
-
-/// Summarizes the information about a completed GitHub item
-#[derive(Debug)]
-struct GitHubWorkItem {
-    /// link to the GitHub page for this item
-    url: String,
-    /// title of the item
-    title: String,
-    /// human-readable summary of the item (generally: `owner/repo#123`)
-    label: String,
-}
-
 impl Item {
-    /// Extract GitHub issue and pull request links
-    fn extract_github_links(&self) -> Vec<GitHubLink> {
-        let github_regex = Regex::new(
-            r"https?://github\.com/(?P<owner>[\w-]+)/(?P<repo>[\w-]+)/(issues|pull)/(?P<number>\d+)"
-        )
-        .unwrap();
-
-        github_regex
-            .captures_iter(&self.content)
-            .filter_map(|caps| {
-                let owner = caps.name("owner")?.as_str().to_string();
-                let repo = caps.name("repo")?.as_str().to_string();
-                let number: u64 = caps.name("number")?.as_str().parse().ok()?;
-                let kind = match caps.get(3)?.as_str() {
-                    "issues" => GitHubKind::Issue,
-                    "pull" => GitHubKind::PullRequest,
-                    _ => return None,
-                };
-
-                Some(GitHubLink { owner, repo, kind, number })
-            })
-            .collect()
+    /// Run every registered provider's extractor over this item's content
+    pub(crate) fn extract_links(
+        &self,
+        link_providers: &[Box<dyn LinkProvider>],
+    ) -> Vec<ParsedLink> {
+        providers::extract_all(link_providers, &self.content)
     }
 
-    /// Fetch the titles of GitHub issues or PRs mentioned in this item
-    async fn fetch_github_titles(
+    /// Look up the already-resolved [`WorkItem`]s for each link mentioned
+    /// in this item
+    pub(crate) fn resolved_work_items<'a>(
         &self,
-        octocrab: &Octocrab,
-    ) -> anyhow::Result<Vec<GitHubWorkItem>> {
-        let mut rv = Vec::new();
-        for link in self.extract_github_links() {
-            let label = format!("{}/{}#{}", link.owner, link.repo, link.number);
-            // eprintln!("note: fetching title for {}", label);
-            match link.kind {
-                GitHubKind::Issue => {
-                    let issue: Issue = match octocrab
-                        .issues(link.owner.clone(), link.repo.clone())
-                        .get(link.number)
-                        .await
-                        .context(format!("Failed to fetch {}", label))
-                    {
-                        Ok(i) => i,
-                        Err(e) => {
-                            eprintln!("warn: {:#}", e);
-                            continue;
-                        }
-                    };
-                    rv.push(GitHubWorkItem {
-                        label,
-                        url: issue.html_url.to_string(),
-                        title: issue.title,
-                    });
-                }
-                GitHubKind::PullRequest => {
-                    let pr: PullRequest = match octocrab
-                        .pulls(link.owner.clone(), link.repo.clone())
-                        .get(link.number)
-                        .await
-                        .context(format!("Failed to fetch {}", label))
-                    {
-                        Ok(p) => p,
-                        Err(e) => {
-                            eprintln!("warn: {:#}", e);
-                            continue;
-                        }
-                    };
-                    let title = pr.title.ok_or_else(|| {
-                        anyhow!("Missing title for {}", label)
-                    })?;
-                    let url = match pr.html_url {
-                        Some(u) => u.to_string(),
-                        None => {
-                            eprintln!("warn: no HTML url for {}", label);
-                            continue;
-                        }
-                    };
-                    rv.push(GitHubWorkItem { label, url, title });
-                }
-            }
-        }
-
-        Ok(rv)
+        link_providers: &[Box<dyn LinkProvider>],
+        resolved: &'a BTreeMap<(String, String), WorkItem>,
+    ) -> Vec<&'a WorkItem> {
+        self.extract_links(link_providers)
+            .iter()
+            .filter_map(|link| resolved.get(&(link.provider.to_string(), link.key.clone())))
+            .collect()
     }
 }
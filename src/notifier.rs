@@ -0,0 +1,90 @@
+//! optional email delivery of the generated report
+//!
+//! modeled on build-o-tron's `notifier.rs`: SMTP configuration is read from
+//! a file rather than hardcoded, recipient addresses are validated, and the
+//! report is sent as a plain-text + HTML multipart message (rendered from
+//! the same [`crate::report::Report`] model the other output formats use).
+
+use crate::report::{Format, Report};
+use anyhow::Context;
+use email_address::EmailAddress;
+use lettre::message::{Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use serde::Deserialize;
+use std::path::Path;
+
+/// SMTP delivery configuration, loaded from a TOML file
+#[derive(Debug, Deserialize)]
+pub struct EmailConfig {
+    pub smtp_host: String,
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+    pub smtp_username: String,
+    pub smtp_password: String,
+    /// address this report is sent from
+    pub from: String,
+    /// addresses this report is sent to
+    pub to: Vec<String>,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+impl EmailConfig {
+    /// Load SMTP delivery configuration from a TOML file at `path`
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<EmailConfig> {
+        let contents =
+            std::fs::read_to_string(path.as_ref()).with_context(|| {
+                format!("failed to read email config at {:?}", path.as_ref())
+            })?;
+        toml::from_str(&contents).with_context(|| {
+            format!("failed to parse email config at {:?}", path.as_ref())
+        })
+    }
+}
+
+/// Render `report` as a plain-text + HTML multipart message and deliver it
+/// over SMTP per `config`
+pub fn send_report(config: &EmailConfig, report: &Report) -> anyhow::Result<()> {
+    let from: Mailbox = config
+        .from
+        .parse()
+        .with_context(|| format!("invalid from address: {}", config.from))?;
+
+    let mut builder =
+        Message::builder().from(from).subject("Status update".to_string());
+    for to in &config.to {
+        if !EmailAddress::is_valid(to) {
+            return Err(anyhow::anyhow!("invalid recipient address: {}", to));
+        }
+        let mailbox: Mailbox = to
+            .parse()
+            .with_context(|| format!("invalid recipient address: {}", to))?;
+        builder = builder.to(mailbox);
+    }
+
+    let plain = report.render(Format::Markdown)?;
+    let html = report.render(Format::Html)?;
+    let message = builder
+        .multipart(
+            MultiPart::alternative()
+                .singlepart(SinglePart::plain(plain))
+                .singlepart(SinglePart::html(html)),
+        )
+        .context("failed to build email message")?;
+
+    let creds = Credentials::new(
+        config.smtp_username.clone(),
+        config.smtp_password.clone(),
+    );
+    let transport = SmtpTransport::starttls_relay(&config.smtp_host)
+        .context("failed to configure SMTP transport")?
+        .port(config.smtp_port)
+        .credentials(creds)
+        .build();
+
+    transport.send(&message).context("failed to send email")?;
+    Ok(())
+}
@@ -0,0 +1,255 @@
+//! the GitHub issue/PR link provider
+//!
+//! Resolution goes through the local title cache (see [`crate::dbctx`])
+//! and retries rate limits and transient failures before giving up on a
+//! link.
+
+use super::{LinkProvider, ParsedLink, WorkItem};
+use crate::CacheOptions;
+use crate::dbctx::DbCtx;
+use anyhow::Context;
+use chrono::Utc;
+use octocrab::{Octocrab, models::issues::Issue, models::pulls::PullRequest};
+use rand::Rng;
+use regex::Regex;
+use std::rc::Rc;
+use std::time::Duration as StdDuration;
+
+/// how many times we'll retry a single link before giving up on it
+const MAX_RETRY_ATTEMPTS: u32 = 5;
+/// ceiling on how long we'll sleep for any single retry, whether that's a
+/// rate-limit reset or an exponential backoff
+const MAX_RETRY_SLEEP: StdDuration = StdDuration::from_secs(120);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitHubKind {
+    Issue,
+    PullRequest,
+}
+
+/// Describes a parsed link to a GitHub issue or pull request
+#[derive(Debug, Clone)]
+pub struct GitHubLink {
+    pub owner: String,
+    pub repo: String,
+    pub kind: GitHubKind,
+    pub number: u64,
+}
+
+impl GitHubLink {
+    fn label(&self) -> String {
+        format!("{}/{}#{}", self.owner, self.repo, self.number)
+    }
+}
+
+pub struct GitHubProvider {
+    octocrab: Octocrab,
+    db: Option<Rc<DbCtx>>,
+    cache: CacheOptions,
+}
+
+impl GitHubProvider {
+    pub fn new(
+        octocrab: Octocrab,
+        db: Option<Rc<DbCtx>>,
+        cache: CacheOptions,
+    ) -> GitHubProvider {
+        GitHubProvider { octocrab, db, cache }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl LinkProvider for GitHubProvider {
+    fn name(&self) -> &'static str {
+        "github"
+    }
+
+    fn extract(&self, content: &str) -> Vec<ParsedLink> {
+        let github_regex = Regex::new(
+            r"https?://github\.com/(?P<owner>[\w-]+)/(?P<repo>[\w-]+)/(issues|pull)/(?P<number>\d+)"
+        )
+        .unwrap();
+
+        github_regex
+            .find_iter(content)
+            .filter_map(|m| {
+                let url = m.as_str().to_string();
+                let link = parse_github_url(&url)?;
+                Some(ParsedLink { provider: self.name(), key: link.label(), url })
+            })
+            .collect()
+    }
+
+    async fn resolve(&self, parsed: &ParsedLink) -> anyhow::Result<WorkItem> {
+        let link = parse_github_url(&parsed.url).ok_or_else(|| {
+            anyhow::anyhow!("not a github issue/PR URL: {}", parsed.url)
+        })?;
+        let label = link.label();
+
+        if !self.cache.no_cache && !self.cache.refresh {
+            if let Some(db) = &self.db {
+                if let Some(cached) = db.lookup(&link, self.cache.ttl)? {
+                    return Ok(cached);
+                }
+            }
+        }
+
+        let octocrab = self.octocrab.clone();
+        let work_item = match link.kind {
+            GitHubKind::Issue => {
+                let (owner, repo, number) =
+                    (link.owner.clone(), link.repo.clone(), link.number);
+                let issue: Issue = with_retry(&label, || {
+                    let octocrab = octocrab.clone();
+                    let owner = owner.clone();
+                    let repo = repo.clone();
+                    async move { octocrab.issues(owner, repo).get(number).await }
+                })
+                .await?;
+                WorkItem {
+                    label: label.clone(),
+                    url: issue.html_url.to_string(),
+                    title: issue.title,
+                }
+            }
+            GitHubKind::PullRequest => {
+                let (owner, repo, number) =
+                    (link.owner.clone(), link.repo.clone(), link.number);
+                let pr: PullRequest = with_retry(&label, || {
+                    let octocrab = octocrab.clone();
+                    let owner = owner.clone();
+                    let repo = repo.clone();
+                    async move { octocrab.pulls(owner, repo).get(number).await }
+                })
+                .await?;
+                let title = pr
+                    .title
+                    .ok_or_else(|| anyhow::anyhow!("missing title for {}", label))?;
+                let url = pr
+                    .html_url
+                    .ok_or_else(|| anyhow::anyhow!("no HTML url for {}", label))?;
+                WorkItem { label: label.clone(), url: url.to_string(), title }
+            }
+        };
+
+        if let Some(db) = &self.db {
+            db.upsert(&link, &work_item, Utc::now())?;
+        }
+
+        Ok(work_item)
+    }
+}
+
+/// Recover a [`GitHubLink`] from a raw GitHub issue/PR URL
+fn parse_github_url(url: &str) -> Option<GitHubLink> {
+    let github_regex = Regex::new(
+        r"https?://github\.com/(?P<owner>[\w-]+)/(?P<repo>[\w-]+)/(issues|pull)/(?P<number>\d+)"
+    )
+    .unwrap();
+
+    let caps = github_regex.captures(url)?;
+    let owner = caps.name("owner")?.as_str().to_string();
+    let repo = caps.name("repo")?.as_str().to_string();
+    let number: u64 = caps.name("number")?.as_str().parse().ok()?;
+    let kind = match caps.get(3)?.as_str() {
+        "issues" => GitHubKind::Issue,
+        "pull" => GitHubKind::PullRequest,
+        _ => return None,
+    };
+
+    Some(GitHubLink { owner, repo, kind, number })
+}
+
+/// Run `attempt` against the GitHub API, retrying on rate limits (a fixed
+/// sleep, capped at [`MAX_RETRY_SLEEP`]) and on transient 5xx errors
+/// (exponential backoff with jitter), up to [`MAX_RETRY_ATTEMPTS`] times.
+async fn with_retry<T, F, Fut>(label: &str, mut attempt: F) -> anyhow::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, octocrab::Error>>,
+{
+    let mut last_error = None;
+
+    for attempt_num in 0..MAX_RETRY_ATTEMPTS {
+        match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(error) => {
+                match classify(&error) {
+                    ErrorClass::RateLimited(reset_delay) => {
+                        let delay = reset_delay.min(MAX_RETRY_SLEEP);
+                        eprintln!(
+                            "note: rate limited fetching {}, sleeping {:?} \
+                             before retrying",
+                            label, delay
+                        );
+                        tokio::time::sleep(delay).await;
+                    }
+                    ErrorClass::Transient => {
+                        let delay = backoff_with_jitter(attempt_num);
+                        eprintln!(
+                            "note: transient error fetching {} ({:#}), \
+                             retrying in {:?}",
+                            label, error, delay
+                        );
+                        tokio::time::sleep(delay).await;
+                    }
+                    ErrorClass::Fatal => {
+                        return Err(error)
+                            .context(format!("failed to fetch {}", label));
+                    }
+                }
+                last_error = Some(error);
+            }
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "giving up on {} after {} attempts: {:#}",
+        label,
+        MAX_RETRY_ATTEMPTS,
+        last_error.unwrap()
+    ))
+}
+
+enum ErrorClass {
+    /// we got a 403/429; retry after sleeping this long
+    RateLimited(StdDuration),
+    /// transient (5xx) failure; worth a backoff-and-retry
+    Transient,
+    /// anything else -- not worth retrying
+    Fatal,
+}
+
+/// how long we sleep on a 429 before retrying
+///
+/// `octocrab::GitHubError` is deserialized from the JSON error body alone;
+/// it doesn't retain the response's `Retry-After` / `X-RateLimit-Reset`
+/// headers, so we can't compute an exact reset time and fall back to a
+/// fixed, conservative sleep instead.
+const RATE_LIMIT_DEFAULT_SLEEP: StdDuration = StdDuration::from_secs(60);
+
+/// Classify an octocrab error for retry purposes, using whatever status
+/// code it carries
+fn classify(error: &octocrab::Error) -> ErrorClass {
+    let octocrab::Error::GitHub { source, .. } = error else {
+        return ErrorClass::Fatal;
+    };
+
+    if source.status_code == http::StatusCode::TOO_MANY_REQUESTS {
+        ErrorClass::RateLimited(RATE_LIMIT_DEFAULT_SLEEP)
+    } else if source.status_code.is_server_error() {
+        ErrorClass::Transient
+    } else {
+        // Notably, this includes plain 403s (expired token, private repo,
+        // deliberately-denied scope, etc.).  Those aren't rate limiting and
+        // should fail fast rather than burning retries on a multi-minute
+        // sleep that won't fix anything.
+        ErrorClass::Fatal
+    }
+}
+
+fn backoff_with_jitter(attempt_num: u32) -> StdDuration {
+    let base = StdDuration::from_secs(2u64.saturating_pow(attempt_num));
+    let jitter_ms = rand::thread_rng().gen_range(0..1000);
+    (base + StdDuration::from_millis(jitter_ms)).min(MAX_RETRY_SLEEP)
+}
@@ -0,0 +1,106 @@
+//! pluggable link extraction and resolution
+//!
+//! Each [`LinkProvider`] knows how to recognize its own kind of URL in free
+//! text and how to turn a recognized link into a [`WorkItem`].  Adding
+//! support for a new kind of tracker (RFDs today, maybe Jira tomorrow)
+//! means writing a new provider, not touching the core fetch loop.
+
+pub mod github;
+pub mod rfd;
+
+use futures::stream::{self, StreamExt};
+use std::collections::BTreeMap;
+
+/// how many links we'll resolve concurrently, across all providers
+const MAX_CONCURRENT_FETCHES: usize = 8;
+
+/// A link recognized by some [`LinkProvider`] in an item's content
+#[derive(Debug, Clone)]
+pub struct ParsedLink {
+    /// name of the provider that recognized this link (see
+    /// [`LinkProvider::name`])
+    pub provider: &'static str,
+    /// provider-chosen key used to dedupe and look up this link's
+    /// [`WorkItem`] once resolved (e.g. `owner/repo#123`, or `RFD 123`)
+    pub key: String,
+    /// the URL as it appeared in the original content
+    pub url: String,
+}
+
+/// Summarizes the information about a resolved work item (a GitHub issue
+/// or PR, an RFD, etc.)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WorkItem {
+    /// link to the item
+    pub url: String,
+    /// title of the item
+    pub title: String,
+    /// human-readable summary of the item (generally: `owner/repo#123`)
+    pub label: String,
+}
+
+/// Recognizes and resolves one kind of link (GitHub issues/PRs, RFDs, etc.)
+#[async_trait::async_trait(?Send)]
+pub trait LinkProvider {
+    /// name of this provider, matched against [`ParsedLink::provider`]
+    fn name(&self) -> &'static str;
+
+    /// Find all links of this provider's kind mentioned in `content`
+    fn extract(&self, content: &str) -> Vec<ParsedLink>;
+
+    /// Resolve a link extracted by this provider into a [`WorkItem`]
+    async fn resolve(&self, link: &ParsedLink) -> anyhow::Result<WorkItem>;
+}
+
+/// Run every provider's extractor over `content`
+pub fn extract_all(
+    providers: &[Box<dyn LinkProvider>],
+    content: &str,
+) -> Vec<ParsedLink> {
+    providers.iter().flat_map(|provider| provider.extract(content)).collect()
+}
+
+/// Resolve `links` concurrently (bounded by [`MAX_CONCURRENT_FETCHES`]),
+/// dispatching each one to the provider that produced it
+///
+/// Links that fail to resolve are logged and omitted from the result
+/// rather than failing the whole run.
+pub async fn resolve_all(
+    providers: &[Box<dyn LinkProvider>],
+    links: impl IntoIterator<Item = ParsedLink>,
+) -> BTreeMap<(String, String), WorkItem> {
+    // Dedupe by (provider, key) -- many items can reference the same link.
+    let mut by_key = BTreeMap::new();
+    for link in links {
+        by_key.entry((link.provider.to_string(), link.key.clone())).or_insert(link);
+    }
+
+    let results = stream::iter(by_key.into_values())
+        .map(|link| async move {
+            let dedupe_key = (link.provider.to_string(), link.key.clone());
+            let result = match providers.iter().find(|p| p.name() == link.provider) {
+                Some(provider) => provider.resolve(&link).await,
+                None => Err(anyhow::anyhow!(
+                    "no provider registered for link provider {:?}",
+                    link.provider
+                )),
+            };
+            (dedupe_key, result)
+        })
+        .buffer_unordered(MAX_CONCURRENT_FETCHES)
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut rv = BTreeMap::new();
+    for (key, result) in results {
+        match result {
+            Ok(item) => {
+                rv.insert(key, item);
+            }
+            Err(error) => {
+                eprintln!("warn: {:#}", error);
+            }
+        }
+    }
+    rv
+}
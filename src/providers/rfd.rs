@@ -0,0 +1,51 @@
+//! the Oxide RFD link provider
+//!
+//! Extraction works today; resolving an RFD number into a title requires
+//! an access token for the RFD site that I don't have yet (see the
+//! top-of-file TODO), so [`RfdProvider::resolve`] just reports that and
+//! lets the caller log-and-skip it like any other failed link.
+
+use super::{LinkProvider, ParsedLink, WorkItem};
+use regex::Regex;
+
+#[derive(Default)]
+pub struct RfdProvider;
+
+impl RfdProvider {
+    pub fn new() -> RfdProvider {
+        RfdProvider
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl LinkProvider for RfdProvider {
+    fn name(&self) -> &'static str {
+        "rfd"
+    }
+
+    fn extract(&self, content: &str) -> Vec<ParsedLink> {
+        let rfd_regex =
+            Regex::new(r"https?://rfd\.shared\.oxide\.computer/rfd/(?P<number>\d+)")
+                .unwrap();
+
+        rfd_regex
+            .captures_iter(content)
+            .filter_map(|caps| {
+                let number = caps.name("number")?.as_str();
+                let url = caps.get(0)?.as_str().to_string();
+                Some(ParsedLink {
+                    provider: self.name(),
+                    key: format!("RFD {}", number),
+                    url,
+                })
+            })
+            .collect()
+    }
+
+    async fn resolve(&self, link: &ParsedLink) -> anyhow::Result<WorkItem> {
+        Err(anyhow::anyhow!(
+            "can't resolve {} yet: no access token configured for the RFD site",
+            link.key
+        ))
+    }
+}
@@ -0,0 +1,98 @@
+//! the report data model shared by every output format
+//!
+//! Fetching and grouping items produces a [`Report`]; rendering it as
+//! Markdown, JSON, or HTML is just a function of that one model, so
+//! `--format` doesn't touch anything upstream of it.
+
+use crate::providers::WorkItem;
+use anyhow::Context;
+use askama::Template;
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+/// A full status report: an ordered list of sections (e.g.
+/// "Reconfigurator", "Other work")
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub sections: Vec<Section>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Section {
+    pub title: String,
+    pub items: Vec<ReportedItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportedItem {
+    pub content: String,
+    pub work_items: Vec<WorkItem>,
+}
+
+/// Which format to render a [`Report`] as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Json,
+    Html,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Format> {
+        match s {
+            "markdown" => Ok(Format::Markdown),
+            "json" => Ok(Format::Json),
+            "html" => Ok(Format::Html),
+            _ => Err(anyhow::anyhow!(
+                "unsupported --format {:?} (expected markdown, json, or html)",
+                s
+            )),
+        }
+    }
+}
+
+impl Report {
+    /// Render this report in the given `format`
+    pub fn render(&self, format: Format) -> anyhow::Result<String> {
+        match format {
+            Format::Markdown => Ok(self.render_markdown()),
+            Format::Json => serde_json::to_string_pretty(self)
+                .context("failed to serialize report as JSON"),
+            Format::Html => HtmlTemplate { report: self }
+                .render()
+                .context("failed to render report as HTML"),
+        }
+    }
+
+    /// Render this report as Markdown, matching the tool's original
+    /// stdout output
+    fn render_markdown(&self) -> String {
+        let mut out = String::new();
+        for (i, section) in self.sections.iter().enumerate() {
+            if i > 0 {
+                let _ = writeln!(out);
+            }
+            let _ = writeln!(out, "{}:", section.title.to_uppercase());
+            for item in &section.items {
+                let _ = writeln!(out, "* {}", item.content);
+                for work_item in &item.work_items {
+                    let _ = writeln!(
+                        out,
+                        "    * [{}]({}) ({:?})",
+                        work_item.label, work_item.url, work_item.title
+                    );
+                }
+            }
+        }
+        out
+    }
+}
+
+#[derive(Template)]
+#[template(path = "report.html")]
+struct HtmlTemplate<'a> {
+    report: &'a Report,
+}
@@ -0,0 +1,110 @@
+//! long-running `--watch` mode: poll for newly-completed tasks and raise a
+//! desktop notification for each one as it appears
+//!
+//! inspired by ghnotify's use of `notify-rust` and `open`.
+
+use crate::providers::{self, LinkProvider, WorkItem};
+use crate::{Item, fetch_completed_tasks};
+use chrono::{DateTime, Utc};
+use notify_rust::Notification;
+use reqwest::Client;
+use std::collections::BTreeSet;
+use std::time::Duration as StdDuration;
+
+/// how often we poll Todoist for newly-completed tasks
+pub const DEFAULT_POLL_INTERVAL: StdDuration = StdDuration::from_secs(5 * 60);
+
+/// Poll `fetch_completed_tasks` every `interval`, raising a desktop
+/// notification for each newly-completed item in an `Oxide:`-prefixed
+/// project.  Runs until killed.
+pub async fn watch(
+    client: &Client,
+    link_providers: &[Box<dyn LinkProvider>],
+    since: DateTime<Utc>,
+    interval: StdDuration,
+) -> anyhow::Result<()> {
+    let mut seen_task_ids = BTreeSet::new();
+
+    // Seed `seen_task_ids` with everything already completed as of `since`
+    // via a silent initial fetch, so the first poll below only notifies on
+    // genuinely new completions rather than replaying the entire (possibly
+    // days-old) backlog.
+    let mut since = since;
+    let seed = fetch_completed_tasks(client, since).await?;
+    for items in seed.values() {
+        for item in items {
+            seen_task_ids.insert(item.task_id.clone());
+        }
+    }
+    since = Utc::now();
+
+    loop {
+        let poll_time = Utc::now();
+        let all_items = fetch_completed_tasks(client, since).await?;
+
+        for (project, items) in &all_items {
+            if !project.starts_with("Oxide") {
+                continue;
+            }
+
+            for item in items {
+                if !seen_task_ids.insert(item.task_id.clone()) {
+                    continue;
+                }
+
+                let links = item.extract_links(link_providers);
+                let resolved = providers::resolve_all(link_providers, links).await;
+                notify_completed(item, link_providers, &resolved);
+            }
+        }
+
+        since = poll_time;
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Raise a desktop notification for a newly-completed item, whose default
+/// action opens the first resolved link in the browser
+///
+/// `NotificationHandle::wait_for_action` blocks until the user acts on or
+/// dismisses the notification, so waiting on it here would stall every
+/// later item in this poll (and the next poll's sleep/fetch) on one
+/// un-acknowledged notification. Hand the wait off to a blocking task so
+/// this function -- and the watch loop that calls it -- can keep going.
+fn notify_completed(
+    item: &Item,
+    link_providers: &[Box<dyn LinkProvider>],
+    resolved: &std::collections::BTreeMap<(String, String), WorkItem>,
+) {
+    let work_items = item.resolved_work_items(link_providers, resolved);
+
+    let mut body = item.content.clone();
+    for work_item in &work_items {
+        body.push_str(&format!("\n{}: {}", work_item.label, work_item.title));
+    }
+
+    let url = work_items.first().map(|work_item| work_item.url.clone());
+
+    let mut notification = Notification::new();
+    notification.summary("Todoist: task completed").body(&body);
+
+    let handle = match notification.show() {
+        Ok(handle) => handle,
+        Err(error) => {
+            eprintln!("warn: failed to show notification: {:#}", error);
+            return;
+        }
+    };
+
+    if let Some(url) = url {
+        tokio::task::spawn_blocking(move || {
+            handle.wait_for_action(|action| {
+                if action == "default" {
+                    if let Err(error) = open::that(&url) {
+                        eprintln!("warn: failed to open {}: {:#}", url, error);
+                    }
+                }
+            });
+        });
+    }
+}